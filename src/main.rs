@@ -1,30 +1,62 @@
+use std::fs;
 use std::io;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
 
-use crossterm::event::{self, Event, KeyCode};
+use crossterm::event::{self, Event, KeyCode, KeyModifiers};
+use directories::ProjectDirs;
 use meval::Expr;
 use ratatui::{
+    buffer::Buffer,
     layout::{Constraint, Direction, Flex, Layout, Rect},
     style::{Color, Modifier, Style, Stylize},
     text::{Line, Span, Text},
-    widgets::{Axis, Block, Borders, Chart, Clear, Dataset, Paragraph},
+    widgets::{
+        Axis, Bar, BarChart, BarGroup, Block, Borders, Chart, Clear, Dataset, Paragraph,
+        StatefulWidget, Widget,
+    },
     DefaultTerminal, Frame,
 };
+use serde::{Deserialize, Serialize};
 
-const SETTINGS_LAYOUT: [[Settings; 4]; 2] = [
-    [
+const SETTINGS_LAYOUT: &[&[Settings]] = &[
+    &[
         Settings::Function,
         Settings::LowerBound,
         Settings::UpperBound,
+        Settings::Method,
         Settings::RecalculateArea,
     ],
-    [
+    &[
         Settings::MinimumX,
         Settings::MaximumX,
         Settings::MinimumY,
         Settings::MaximumY,
     ],
+    &[
+        Settings::ShowRectangles,
+        Settings::RectangleCount,
+        Settings::RectangleMethod,
+        Settings::Animate,
+    ],
 ];
 
+// ~30 fps: fast enough to feel smooth, slow enough not to busy-loop.
+const TICK_RATE: Duration = Duration::from_millis(33);
+// Number of ticks for the animated bound sweep to cross the full interval.
+const ANIMATION_TICKS: u32 = 90;
+// Roughly how many samples to keep across the visible x-span regardless of
+// zoom level, so zooming out doesn't blow up `self.data`.
+const TARGET_SAMPLE_COUNT: f64 = 2000.0;
+// Fraction of the visible span that a single pan key-press moves the window.
+const PAN_STEP_FRACTION: f64 = 0.1;
+// Multiplicative factor a single +/- key-press zooms the window by.
+const ZOOM_STEP_FACTOR: f64 = 0.8;
+// Smallest sample spacing we'll honor, from user input or a saved session,
+// so a stray zero/negative `dx` can't turn `populate_data`'s sampling loop
+// into an infinite loop.
+const MIN_DX: f64 = 1e-6;
+
 struct App<'a> {
     function_text: String,
     expression: Expr,
@@ -43,6 +75,17 @@ struct App<'a> {
     window_y: [f64; 2],
     window_y_text: Vec<String>,
     area: f64,
+    method: IntegrationMethod,
+    error_estimate: Option<f64>,
+    show_rectangles: bool,
+    rectangle_count: usize,
+    rectangle_count_text: String,
+    rectangle_method: RectangleMethod,
+    rectangles: Vec<RectangleStrip>,
+    animating: bool,
+    animation_x: f64,
+    chart_view_state: ChartViewState,
+    last_error: Option<String>,
     active_screen: CurrentScreen,
     settings_focus: &'a Settings,
     settings_position_x: usize,
@@ -60,11 +103,152 @@ enum Settings {
     Function,
     LowerBound,
     UpperBound,
+    Method,
     RecalculateArea,
     MinimumX,
     MinimumY,
     MaximumX,
     MaximumY,
+    ShowRectangles,
+    RectangleCount,
+    RectangleMethod,
+    Animate,
+}
+
+// Subset of `App`'s fields persisted across runs by `App::save`/`App::load`.
+#[derive(Serialize, Deserialize)]
+struct SessionConfig {
+    function_text: String,
+    bounds_text: Vec<String>,
+    window_x_text: Vec<String>,
+    window_y_text: Vec<String>,
+    dx: f64,
+}
+
+// A single strip of the Riemann sum between `bounds[0]` and `bounds[1]`,
+// sampled according to `RectangleMethod`.
+struct RectangleStrip {
+    left: f64,
+    contribution: f64,
+}
+
+// State for `ChartView`, carried on `App` so the viewport survives across
+// `draw` calls instead of resetting every frame.
+struct ChartViewState {
+    window_x: [f64; 2],
+    window_y: [f64; 2],
+}
+
+// Wraps the function/bound-line/axis datasets so the chart can be rendered
+// through `frame.render_stateful_widget`, with the viewport coming from
+// `ChartViewState` rather than being baked into the widget itself.
+struct ChartView<'a> {
+    datasets: Vec<Dataset<'a>>,
+}
+
+impl StatefulWidget for ChartView<'_> {
+    type State = ChartViewState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let x_labels = [
+            Span::styled(
+                format!("{}", state.window_x[0]),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("{}", (state.window_x[0] + state.window_x[1]) / 2.0),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("{}", state.window_x[1]),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+        ];
+
+        let y_labels = [
+            Span::styled(
+                format!("{}", state.window_y[0]),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("{}", (state.window_y[0] + state.window_y[1]) / 2.0),
+                Style::default(),
+            ),
+            Span::styled(
+                format!("{}", state.window_y[1]),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+        ];
+
+        let chart = Chart::new(self.datasets)
+            .block(Block::bordered())
+            .x_axis(
+                Axis::default()
+                    .title("X Axis")
+                    .style(Style::default().fg(Color::Gray))
+                    .labels(x_labels)
+                    .bounds(state.window_x),
+            )
+            .y_axis(
+                Axis::default()
+                    .title("Y Axis")
+                    .style(Style::default().fg(Color::Gray))
+                    .labels(y_labels)
+                    .bounds(state.window_y),
+            );
+
+        Widget::render(chart, area, buf);
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum RectangleMethod {
+    Left,
+    Right,
+    Midpoint,
+}
+
+impl RectangleMethod {
+    fn label(&self) -> &'static str {
+        match self {
+            RectangleMethod::Left => "Left",
+            RectangleMethod::Right => "Right",
+            RectangleMethod::Midpoint => "Midpoint",
+        }
+    }
+
+    fn next(self) -> Self {
+        match self {
+            RectangleMethod::Left => RectangleMethod::Right,
+            RectangleMethod::Right => RectangleMethod::Midpoint,
+            RectangleMethod::Midpoint => RectangleMethod::Left,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum IntegrationMethod {
+    Trapezoid,
+    CompositeSimpson,
+    AdaptiveSimpson,
+}
+
+impl IntegrationMethod {
+    fn label(&self) -> &'static str {
+        match self {
+            IntegrationMethod::Trapezoid => "Trapezoid",
+            IntegrationMethod::CompositeSimpson => "Composite Simpson",
+            IntegrationMethod::AdaptiveSimpson => "Adaptive Simpson",
+        }
+    }
+
+    fn next(self) -> Self {
+        match self {
+            IntegrationMethod::Trapezoid => IntegrationMethod::CompositeSimpson,
+            IntegrationMethod::CompositeSimpson => IntegrationMethod::AdaptiveSimpson,
+            IntegrationMethod::AdaptiveSimpson => IntegrationMethod::Trapezoid,
+        }
+    }
 }
 
 impl App<'_> {
@@ -85,6 +269,20 @@ impl App<'_> {
             window_y: [-10.0, 10.0],
             window_y_text: vec!["-10".to_string(), "10".to_string()],
             area: 0.0,
+            method: IntegrationMethod::Trapezoid,
+            error_estimate: None,
+            show_rectangles: false,
+            rectangle_count: 20,
+            rectangle_count_text: "20".to_string(),
+            rectangle_method: RectangleMethod::Left,
+            rectangles: Vec::new(),
+            animating: false,
+            animation_x: 0.0,
+            chart_view_state: ChartViewState {
+                window_x: [-5.0, 5.0],
+                window_y: [-10.0, 10.0],
+            },
+            last_error: None,
             active_screen: CurrentScreen::Main,
             settings_focus: &Settings::Function,
             settings_position_x: 0,
@@ -119,13 +317,47 @@ impl App<'_> {
         self.populate_x_axis_line();
 
         self.calculate_area();
+        self.populate_rectangles();
+    }
+
+    // Samples the Riemann-sum rectangles between `bounds[0]` and
+    // `bounds[1]` so `draw_chart` can render them as a `BarChart` next to
+    // the curve.
+    fn populate_rectangles(&mut self) {
+        self.rectangles.clear();
+
+        if !self.show_rectangles || self.rectangle_count == 0 {
+            return;
+        }
+
+        let function = self.expression.clone().bind("x").unwrap();
+        let width = (self.bounds[1] - self.bounds[0]) / self.rectangle_count as f64;
+
+        for i in 0..self.rectangle_count {
+            let left = self.bounds[0] + i as f64 * width;
+            let sample_x = match self.rectangle_method {
+                RectangleMethod::Left => left,
+                RectangleMethod::Right => left + width,
+                RectangleMethod::Midpoint => left + width / 2.0,
+            };
+            let height = function(sample_x);
+
+            self.rectangles.push(RectangleStrip {
+                left,
+                contribution: height * width,
+            });
+        }
     }
 
     fn populate_upper_bound_line(&mut self) {
+        self.populate_upper_bound_line_at(self.bounds[1]);
+    }
+
+    // Same as `populate_upper_bound_line`, but at an arbitrary `x` so the
+    // animated sweep can move the line without touching `self.bounds[1]`.
+    fn populate_upper_bound_line_at(&mut self, x: f64) {
         self.upper_bound_line.clear();
 
-        // Set x to the upper bound
-        let x = self.bounds[1];
         let function = self.expression.clone().bind("x").unwrap();
         let height = function(x);
         let mut y = self.window_y[0];
@@ -167,26 +399,223 @@ impl App<'_> {
 
     fn calculate_area(&mut self) {
         self.area = 0.0;
+        self.error_estimate = None;
+
+        let (Some(lo), Some(hi)) = self.limits_indexs else {
+            self.last_error = Some("bounds outside plotted range".to_string());
+            return;
+        };
+
+        if lo >= hi {
+            self.last_error = Some("bounds outside plotted range".to_string());
+            return;
+        }
+
+        match self.method {
+            IntegrationMethod::Trapezoid => {
+                self.area = self.data[lo..hi]
+                    .windows(2)
+                    .map(|window| {
+                        let ((_, y1), (_, y2)) = (window[0], window[1]);
+                        self.dx * (y2 + y1) / 2.0
+                    })
+                    .sum::<f64>();
+            }
+            IntegrationMethod::CompositeSimpson => {
+                self.area = self.composite_simpson(lo, hi);
+            }
+            IntegrationMethod::AdaptiveSimpson => {
+                let function = self.expression.clone().bind("x").unwrap();
+                let (area, error) =
+                    Self::adaptive_simpson(&function, self.bounds[0], self.bounds[1], 1e-6, 50);
+                self.area = area;
+                self.error_estimate = Some(error);
+            }
+        }
+
+        self.last_error = None;
+    }
+
+    // Composite Simpson's rule over the already-sampled points between
+    // `lo` and `hi`. Simpson needs an even number of strips, so an odd
+    // leftover strip at the end is folded in with a single trapezoid.
+    fn composite_simpson(&self, lo: usize, hi: usize) -> f64 {
+        let points = &self.data[lo..hi];
+        let intervals = points.len() - 1;
+
+        // Simpson needs at least two strips; fewer than that is just a
+        // trapezoid (or, with a single sample, nothing to integrate).
+        if intervals < 2 {
+            return points
+                .windows(2)
+                .map(|window| {
+                    let ((_, y1), (_, y2)) = (window[0], window[1]);
+                    self.dx * (y2 + y1) / 2.0
+                })
+                .sum();
+        }
+
+        let even_intervals = intervals - (intervals % 2);
+
+        let mut sum = points[0].1 + points[even_intervals].1;
+        for (i, (_, y)) in points.iter().enumerate().take(even_intervals).skip(1) {
+            sum += if i % 2 == 1 { 4.0 * y } else { 2.0 * y };
+        }
+        let mut area = self.dx / 3.0 * sum;
 
-        self.area = self.data[self.limits_indexs.0.unwrap()..self.limits_indexs.1.unwrap()]
+        if even_intervals < intervals {
+            let (_, y1) = points[even_intervals];
+            let (_, y2) = points[even_intervals + 1];
+            area += self.dx * (y1 + y2) / 2.0;
+        }
+
+        area
+    }
+
+    // Adaptive Simpson quadrature: recursively halves `[a, b]` until the
+    // Simpson estimate over the whole interval agrees with the sum of the
+    // two half-interval estimates to within `15 * eps`, per Richardson
+    // extrapolation. Returns `(area, accumulated error estimate)`.
+    fn adaptive_simpson<F: Fn(f64) -> f64>(f: &F, a: f64, b: f64, eps: f64, depth: u32) -> (f64, f64) {
+        let simpson = |a: f64, b: f64| (b - a) / 6.0 * (f(a) + 4.0 * f((a + b) / 2.0) + f(b));
+
+        let whole = simpson(a, b);
+        let m = (a + b) / 2.0;
+        let left = simpson(a, m);
+        let right = simpson(m, b);
+        let delta = left + right - whole;
+
+        if depth == 0 || delta.abs() <= 15.0 * eps {
+            (left + right + delta / 15.0, delta / 15.0)
+        } else {
+            let (left_area, left_error) = Self::adaptive_simpson(f, a, m, eps / 2.0, depth - 1);
+            let (right_area, right_error) = Self::adaptive_simpson(f, m, b, eps / 2.0, depth - 1);
+            (left_area + right_area, left_error + right_error)
+        }
+    }
+
+    // Trapezoidal area from the lower bound up to `upper`, used while the
+    // bound sweep animation is in progress so each tick shows the area
+    // accumulating without disturbing `self.bounds[1]`.
+    fn calculate_area_up_to(&self, upper: f64) -> f64 {
+        let Some(lo) = self.limits_indexs.0 else {
+            return 0.0;
+        };
+        let hi = self
+            .data
+            .iter()
+            .position(|(x, _)| *x >= upper)
+            .unwrap_or(self.data.len());
+
+        if hi <= lo {
+            return 0.0;
+        }
+
+        self.data[lo..hi]
             .windows(2)
             .map(|window| {
                 let ((_, y1), (_, y2)) = (window[0], window[1]);
                 self.dx * (y2 + y1) / 2.0
             })
-            .sum::<f64>();
+            .sum::<f64>()
+    }
+
+    fn start_animation(&mut self) {
+        if self.limits_indexs.0.is_none() || self.limits_indexs.1.is_none() {
+            self.last_error = Some("bounds outside plotted range".to_string());
+            return;
+        }
+
+        self.animating = true;
+        self.animation_x = self.bounds[0];
+    }
+
+    fn on_tick(&mut self) {
+        if !self.animating {
+            return;
+        }
+
+        let step = (self.bounds[1] - self.bounds[0]) / ANIMATION_TICKS as f64;
+        self.animation_x += step;
+
+        if (step >= 0.0 && self.animation_x >= self.bounds[1])
+            || (step < 0.0 && self.animation_x <= self.bounds[1])
+        {
+            self.animating = false;
+            self.populate_upper_bound_line();
+            self.calculate_area();
+            return;
+        }
+
+        self.populate_upper_bound_line_at(self.animation_x);
+        self.area = self.calculate_area_up_to(self.animation_x);
+    }
+
+    // Slides the viewport by a fraction of its current span in each axis.
+    // `dx`/`dy` are in units of "one key-press", e.g. `(-1.0, 0.0)` for Left.
+    fn pan(&mut self, dx: f64, dy: f64) {
+        let step_x = (self.window_x[1] - self.window_x[0]) * PAN_STEP_FRACTION * dx;
+        let step_y = (self.window_y[1] - self.window_y[0]) * PAN_STEP_FRACTION * dy;
+
+        self.window_x[0] += step_x;
+        self.window_x[1] += step_x;
+        self.window_y[0] += step_y;
+        self.window_y[1] += step_y;
+
+        self.sync_window_to_view();
+    }
+
+    // Scales the viewport around its center. `factor < 1.0` zooms in,
+    // `factor > 1.0` zooms out.
+    fn zoom(&mut self, factor: f64) {
+        let center_x = (self.window_x[0] + self.window_x[1]) / 2.0;
+        let half_span_x = (self.window_x[1] - self.window_x[0]) / 2.0 * factor;
+        self.window_x = [center_x - half_span_x, center_x + half_span_x];
+
+        let center_y = (self.window_y[0] + self.window_y[1]) / 2.0;
+        let half_span_y = (self.window_y[1] - self.window_y[0]) / 2.0 * factor;
+        self.window_y = [center_y - half_span_y, center_y + half_span_y];
+
+        self.sync_window_to_view();
+    }
+
+    // Pushes `window_x`/`window_y` out to the settings text fields and
+    // re-samples with a density tied to the new x-span — the shared tail end
+    // of both `pan` and `zoom`. The chart state picks up the new window in
+    // `draw_chart`.
+    fn sync_window_to_view(&mut self) {
+        self.window_x_text[0] = format!("{:.4}", self.window_x[0]);
+        self.window_x_text[1] = format!("{:.4}", self.window_x[1]);
+        self.window_y_text[0] = format!("{:.4}", self.window_y[0]);
+        self.window_y_text[1] = format!("{:.4}", self.window_y[1]);
+
+        let span = (self.window_x[1] - self.window_x[0]).abs();
+        self.dx = (span / TARGET_SAMPLE_COUNT).max(MIN_DX);
+
+        self.populate_data();
     }
 
     fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        let mut last_tick = Instant::now();
+
         while !self.exit {
             terminal.draw(|frame| self.draw(frame)).unwrap();
-            self.handle_events().unwrap();
+
+            let timeout = TICK_RATE.saturating_sub(last_tick.elapsed());
+            if event::poll(timeout)? {
+                self.handle_event(event::read()?)?;
+            }
+
+            if last_tick.elapsed() >= TICK_RATE {
+                self.on_tick();
+                last_tick = Instant::now();
+            }
         }
 
         Ok(())
     }
 
-    fn draw(&self, frame: &mut Frame) {
+    fn draw(&mut self, frame: &mut Frame) {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
@@ -206,7 +635,11 @@ impl App<'_> {
         ))
         .block(title_block);
 
-        let area_footer = Paragraph::new(Line::from(format!("{:.4}", self.area)))
+        let area_text = match self.error_estimate {
+            Some(error) => format!("{:.4} (estimated error: {:.2e})", self.area, error),
+            None => format!("{:.4}", self.area),
+        };
+        let area_footer = Paragraph::new(Line::from(area_text))
             .block(Block::default().title("Area").borders(Borders::ALL));
 
         self.draw_chart(frame, chunks[1]);
@@ -224,15 +657,19 @@ impl App<'_> {
         let vertical_chunks = Layout::default()
             .direction(Direction::Vertical)
             .margin(1)
-            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .constraints([
+                Constraint::Percentage(34),
+                Constraint::Percentage(33),
+                Constraint::Percentage(33),
+                Constraint::Length(1),
+            ])
             .split(area_to_draw);
+        let top_row = SETTINGS_LAYOUT[0];
         let top_horizontal_chunks = Layout::default()
             .direction(Direction::Horizontal)
-            .constraints([
-                Constraint::Percentage(25),
-                Constraint::Percentage(25),
-                Constraint::Percentage(25),
-                Constraint::Percentage(25),
+            .constraints(vec![
+                Constraint::Percentage(100 / top_row.len() as u16);
+                top_row.len()
             ])
             .split(vertical_chunks[0]);
         let bottom_horizontal_chunks = Layout::default()
@@ -244,6 +681,14 @@ impl App<'_> {
                 Constraint::Percentage(25),
             ])
             .split(vertical_chunks[1]);
+        let rectangles_row = SETTINGS_LAYOUT[2];
+        let rectangles_horizontal_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(vec![
+                Constraint::Percentage(100 / rectangles_row.len() as u16);
+                rectangles_row.len()
+            ])
+            .split(vertical_chunks[2]);
 
         let function_block = Paragraph::new(Line::from(Span::styled(
             &self.function_text,
@@ -280,6 +725,15 @@ impl App<'_> {
                 .borders(Borders::ALL),
         );
 
+        let method_block = Paragraph::new(Line::from(Span::styled(
+            self.method.label(),
+            match self.settings_focus {
+                Settings::Method => Style::default().fg(Color::Red),
+                _ => Style::default(),
+            },
+        )))
+        .block(Block::default().title("Method").borders(Borders::ALL));
+
         let recalculate_area_block = Paragraph::new(Line::from(Span::styled(
             "Recalculate Area",
             match self.settings_focus {
@@ -330,18 +784,75 @@ impl App<'_> {
         )))
         .block(Block::default().title("Maximum Y").borders(Borders::ALL));
 
+        let show_rectangles_block = Paragraph::new(Line::from(Span::styled(
+            if self.show_rectangles { "On" } else { "Off" },
+            match self.settings_focus {
+                Settings::ShowRectangles => Style::default().fg(Color::Red),
+                _ => Style::default(),
+            },
+        )))
+        .block(
+            Block::default()
+                .title("Show Rectangles")
+                .borders(Borders::ALL),
+        );
+
+        let rectangle_count_block = Paragraph::new(Line::from(Span::styled(
+            &self.rectangle_count_text,
+            match self.settings_focus {
+                Settings::RectangleCount => Style::default().fg(Color::Red),
+                _ => Style::default(),
+            },
+        )))
+        .block(
+            Block::default()
+                .title("Rectangle Count")
+                .borders(Borders::ALL),
+        );
+
+        let rectangle_method_block = Paragraph::new(Line::from(Span::styled(
+            self.rectangle_method.label(),
+            match self.settings_focus {
+                Settings::RectangleMethod => Style::default().fg(Color::Red),
+                _ => Style::default(),
+            },
+        )))
+        .block(Block::default().title("Sample Point").borders(Borders::ALL));
+
+        let animate_block = Paragraph::new(Line::from(Span::styled(
+            if self.animating { "Animating..." } else { "Animate" },
+            match self.settings_focus {
+                Settings::Animate => Style::default().fg(Color::Red),
+                _ => Style::default(),
+            },
+        )))
+        .block(Block::default().borders(Borders::ALL));
+
+        let error_line = Paragraph::new(Line::from(Span::styled(
+            self.last_error.as_deref().unwrap_or(""),
+            Style::default().fg(Color::Red),
+        )));
+
         frame.render_widget(Clear, area_to_draw);
         frame.render_widget(settings_block, area_to_draw);
 
         frame.render_widget(function_block, top_horizontal_chunks[0]);
         frame.render_widget(lower_bound_block, top_horizontal_chunks[1]);
         frame.render_widget(upper_bound_block, top_horizontal_chunks[2]);
-        frame.render_widget(recalculate_area_block, top_horizontal_chunks[3]);
+        frame.render_widget(method_block, top_horizontal_chunks[3]);
+        frame.render_widget(recalculate_area_block, top_horizontal_chunks[4]);
 
         frame.render_widget(min_x_block, bottom_horizontal_chunks[0]);
         frame.render_widget(max_x_block, bottom_horizontal_chunks[1]);
         frame.render_widget(min_y_block, bottom_horizontal_chunks[2]);
         frame.render_widget(max_y_block, bottom_horizontal_chunks[3]);
+
+        frame.render_widget(show_rectangles_block, rectangles_horizontal_chunks[0]);
+        frame.render_widget(rectangle_count_block, rectangles_horizontal_chunks[1]);
+        frame.render_widget(rectangle_method_block, rectangles_horizontal_chunks[2]);
+        frame.render_widget(animate_block, rectangles_horizontal_chunks[3]);
+
+        frame.render_widget(error_line, vertical_chunks[3]);
     }
 
     fn popup_area(&self, area: Rect, percent_x: u16, percent_y: u16) -> Rect {
@@ -352,36 +863,17 @@ impl App<'_> {
         area
     }
 
-    fn draw_chart(&self, frame: &mut Frame, area_to_draw: Rect) {
-        let x_labels = [
-            Span::styled(
-                format!("{}", self.window_x[0]),
-                Style::default().add_modifier(Modifier::BOLD),
-            ),
-            Span::styled(
-                format!("{}", (self.window_x[0] + self.window_x[1]) / 2.0),
-                Style::default().add_modifier(Modifier::BOLD),
-            ),
-            Span::styled(
-                format!("{}", self.window_x[1]),
-                Style::default().add_modifier(Modifier::BOLD),
-            ),
-        ];
-
-        let y_labels = [
-            Span::styled(
-                format!("{}", self.window_y[0]),
-                Style::default().add_modifier(Modifier::BOLD),
-            ),
-            Span::styled(
-                format!("{}", (self.window_y[0] + self.window_y[1]) / 2.0),
-                Style::default(),
-            ),
-            Span::styled(
-                format!("{}", self.window_y[1]),
-                Style::default().add_modifier(Modifier::BOLD),
-            ),
-        ];
+    fn draw_chart(&mut self, frame: &mut Frame, area_to_draw: Rect) {
+        let area_to_draw = if self.show_rectangles {
+            let chunks = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(70), Constraint::Percentage(30)])
+                .split(area_to_draw);
+            self.draw_rectangles_bar_chart(frame, chunks[1]);
+            chunks[0]
+        } else {
+            area_to_draw
+        };
 
         let dataset = vec![
             Dataset::default()
@@ -406,116 +898,231 @@ impl App<'_> {
                 .data(&self.data),
         ];
 
-        let chart = Chart::new(dataset)
-            .block(Block::bordered())
-            .x_axis(
-                Axis::default()
-                    .title("X Axis")
-                    .style(Style::default().fg(Color::Gray))
-                    .labels(x_labels)
-                    .bounds(self.window_x),
+        self.chart_view_state.window_x = self.window_x;
+        self.chart_view_state.window_y = self.window_y;
+
+        let view = ChartView { datasets: dataset };
+        frame.render_stateful_widget(view, area_to_draw, &mut self.chart_view_state);
+    }
+
+    // Renders the Riemann-sum rectangles as a bar per strip, labeled with the
+    // strip's left edge. Bar heights are the scaled absolute contribution so
+    // they stay readable, while the value text shows the exact signed
+    // contribution (`height * width`) of that strip to the Riemann sum.
+    fn draw_rectangles_bar_chart(&self, frame: &mut Frame, area_to_draw: Rect) {
+        const SCALE: f64 = 1000.0;
+
+        let bars: Vec<Bar> = self
+            .rectangles
+            .iter()
+            .map(|strip| {
+                let color = if strip.contribution >= 0.0 {
+                    Color::Green
+                } else {
+                    Color::Red
+                };
+
+                Bar::default()
+                    .value((strip.contribution.abs() * SCALE).round() as u64)
+                    .label(Line::from(format!("{:.2}", strip.left)))
+                    .text_value(format!("{:.4}", strip.contribution))
+                    .style(Style::default().fg(color))
+            })
+            .collect();
+
+        let bar_chart = BarChart::default()
+            .block(
+                Block::bordered()
+                    .title(format!("{} Riemann Sum", self.rectangle_method.label())),
             )
-            .y_axis(
-                Axis::default()
-                    .title("Y Axis")
-                    .style(Style::default().fg(Color::Gray))
-                    .labels(y_labels)
-                    .bounds(self.window_y),
-            );
+            .data(BarGroup::default().bars(&bars))
+            .bar_width(3)
+            .bar_gap(1);
 
-        frame.render_widget(chart, area_to_draw);
+        frame.render_widget(bar_chart, area_to_draw);
     }
 
-    fn handle_events(&mut self) -> io::Result<()> {
-        if let Event::Key(key_event) = event::read()? {
+    fn handle_event(&mut self, event: Event) -> io::Result<()> {
+        if let Event::Key(key_event) = event {
             match key_event.code {
+                KeyCode::Char('s') if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+                    self.save();
+                }
+                KeyCode::Char('l') if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+                    self.load();
+                }
+                KeyCode::Char(character) if self.active_screen == CurrentScreen::Main => {
+                    match character {
+                        '+' | '=' => self.zoom(ZOOM_STEP_FACTOR),
+                        '-' | '_' => self.zoom(1.0 / ZOOM_STEP_FACTOR),
+                        _ => {}
+                    }
+                }
                 KeyCode::Char(character) => match self.settings_focus {
                     Settings::Function => self.function_text.push(character),
                     Settings::LowerBound => self.bounds_text[0].push(character),
                     Settings::UpperBound => self.bounds_text[1].push(character),
+                    Settings::Method => {}
                     Settings::RecalculateArea => {}
                     Settings::MinimumX => self.window_x_text[0].push(character),
                     Settings::MaximumX => self.window_x_text[1].push(character),
                     Settings::MinimumY => self.window_y_text[0].push(character),
                     Settings::MaximumY => self.window_y_text[1].push(character),
+                    Settings::ShowRectangles => {}
+                    Settings::RectangleCount => self.rectangle_count_text.push(character),
+                    Settings::RectangleMethod => {}
+                    Settings::Animate => {}
                 },
                 KeyCode::Backspace => match self.settings_focus {
                     Settings::Function => _ = self.function_text.pop(),
                     Settings::LowerBound => _ = self.bounds_text[0].pop(),
                     Settings::UpperBound => _ = self.bounds_text[1].pop(),
+                    Settings::Method => {}
                     Settings::RecalculateArea => {}
                     Settings::MinimumX => _ = self.window_x_text[0].pop(),
                     Settings::MaximumX => _ = self.window_x_text[1].pop(),
                     Settings::MinimumY => _ = self.window_y_text[0].pop(),
                     Settings::MaximumY => _ = self.window_y_text[1].pop(),
+                    Settings::ShowRectangles => {}
+                    Settings::RectangleCount => _ = self.rectangle_count_text.pop(),
+                    Settings::RectangleMethod => {}
+                    Settings::Animate => {}
                 },
                 KeyCode::Enter => match self.settings_focus {
-                    Settings::Function => {
-                        self.expression = self.function_text.parse().unwrap();
-                        self.populate_data();
-                    }
-                    Settings::LowerBound => {
-                        self.bounds[0] = self.bounds_text[0].parse::<f64>().unwrap();
-                        self.populate_data();
-                    }
-                    Settings::UpperBound => {
-                        self.bounds[1] = self.bounds_text[1].parse::<f64>().unwrap();
-                        self.populate_data();
+                    Settings::Function => match self.function_text.parse::<Expr>() {
+                        Ok(expression) => match expression.clone().bind("x") {
+                            Ok(_) => {
+                                self.expression = expression;
+                                self.last_error = None;
+                                self.populate_data();
+                            }
+                            Err(err) => {
+                                self.last_error = Some(format!("invalid function: {err}"))
+                            }
+                        },
+                        Err(err) => self.last_error = Some(format!("invalid function: {err}")),
+                    },
+                    Settings::LowerBound => match self.bounds_text[0].parse::<f64>() {
+                        Ok(value) => {
+                            self.bounds[0] = value;
+                            self.last_error = None;
+                            self.populate_data();
+                        }
+                        Err(err) => self.last_error = Some(format!("invalid lower bound: {err}")),
+                    },
+                    Settings::UpperBound => match self.bounds_text[1].parse::<f64>() {
+                        Ok(value) => {
+                            self.bounds[1] = value;
+                            self.last_error = None;
+                            self.populate_data();
+                        }
+                        Err(err) => self.last_error = Some(format!("invalid upper bound: {err}")),
+                    },
+                    Settings::Method => {
+                        self.method = self.method.next();
+                        self.calculate_area();
                     }
                     Settings::RecalculateArea => self.calculate_area(),
-                    Settings::MinimumX => {
-                        self.window_x[0] = self.window_x_text[0].parse().unwrap();
-                        self.populate_data();
-                    }
-                    Settings::MaximumX => {
-                        self.window_x[1] = self.window_x_text[1].parse().unwrap();
-                        self.populate_data();
+                    Settings::MinimumX => match self.window_x_text[0].parse::<f64>() {
+                        Ok(value) => {
+                            self.window_x[0] = value;
+                            self.last_error = None;
+                            self.populate_data();
+                        }
+                        Err(err) => self.last_error = Some(format!("invalid minimum x: {err}")),
+                    },
+                    Settings::MaximumX => match self.window_x_text[1].parse::<f64>() {
+                        Ok(value) => {
+                            self.window_x[1] = value;
+                            self.last_error = None;
+                            self.populate_data();
+                        }
+                        Err(err) => self.last_error = Some(format!("invalid maximum x: {err}")),
+                    },
+                    Settings::MinimumY => match self.window_y_text[0].parse::<f64>() {
+                        Ok(value) => {
+                            self.window_y[0] = value;
+                            self.last_error = None;
+                            self.populate_data();
+                        }
+                        Err(err) => self.last_error = Some(format!("invalid minimum y: {err}")),
+                    },
+                    Settings::MaximumY => match self.window_y_text[1].parse::<f64>() {
+                        Ok(value) => {
+                            self.window_y[1] = value;
+                            self.last_error = None;
+                            self.populate_data();
+                        }
+                        Err(err) => self.last_error = Some(format!("invalid maximum y: {err}")),
+                    },
+                    Settings::ShowRectangles => {
+                        self.show_rectangles = !self.show_rectangles;
+                        self.populate_rectangles();
                     }
-                    Settings::MinimumY => {
-                        self.window_y[0] = self.window_y_text[0].parse().unwrap();
-                        self.populate_data();
-                    }
-                    Settings::MaximumY => {
-                        self.window_y[1] = self.window_y_text[1].parse().unwrap();
-                        self.populate_data();
+                    Settings::RectangleCount => match self.rectangle_count_text.parse::<usize>() {
+                        Ok(value) => {
+                            self.rectangle_count = value;
+                            self.last_error = None;
+                            self.populate_rectangles();
+                        }
+                        Err(err) => {
+                            self.last_error = Some(format!("invalid rectangle count: {err}"))
+                        }
+                    },
+                    Settings::RectangleMethod => {
+                        self.rectangle_method = self.rectangle_method.next();
+                        self.populate_rectangles();
                     }
+                    Settings::Animate => self.start_animation(),
                 },
-                KeyCode::Left => {
-                    if self.settings_position_x != 0
-                        && self.active_screen == CurrentScreen::Settings
-                    {
-                        self.settings_position_x -= 1;
-                        self.settings_focus =
-                            &SETTINGS_LAYOUT[self.settings_position_y][self.settings_position_x];
+                KeyCode::Left => match self.active_screen {
+                    CurrentScreen::Settings => {
+                        if self.settings_position_x != 0 {
+                            self.settings_position_x -= 1;
+                            self.settings_focus = &SETTINGS_LAYOUT[self.settings_position_y]
+                                [self.settings_position_x];
+                        }
                     }
-                }
-                KeyCode::Right => {
-                    if self.settings_position_x != 3
-                        && self.active_screen == CurrentScreen::Settings
-                    {
-                        self.settings_position_x += 1;
-                        self.settings_focus =
-                            &SETTINGS_LAYOUT[self.settings_position_y][self.settings_position_x];
+                    CurrentScreen::Main => self.pan(-1.0, 0.0),
+                },
+                KeyCode::Right => match self.active_screen {
+                    CurrentScreen::Settings => {
+                        if self.settings_position_x + 1
+                            < SETTINGS_LAYOUT[self.settings_position_y].len()
+                        {
+                            self.settings_position_x += 1;
+                            self.settings_focus = &SETTINGS_LAYOUT[self.settings_position_y]
+                                [self.settings_position_x];
+                        }
                     }
-                }
-                KeyCode::Up => {
-                    if self.settings_position_y != 0
-                        && self.active_screen == CurrentScreen::Settings
-                    {
-                        self.settings_position_y -= 1;
-                        self.settings_focus =
-                            &SETTINGS_LAYOUT[self.settings_position_y][self.settings_position_x];
+                    CurrentScreen::Main => self.pan(1.0, 0.0),
+                },
+                KeyCode::Up => match self.active_screen {
+                    CurrentScreen::Settings => {
+                        if self.settings_position_y != 0 {
+                            self.settings_position_y -= 1;
+                            self.settings_position_x = self
+                                .settings_position_x
+                                .min(SETTINGS_LAYOUT[self.settings_position_y].len() - 1);
+                            self.settings_focus = &SETTINGS_LAYOUT[self.settings_position_y]
+                                [self.settings_position_x];
+                        }
                     }
-                }
-                KeyCode::Down => {
-                    if self.settings_position_y != 1
-                        && self.active_screen == CurrentScreen::Settings
-                    {
-                        self.settings_position_y += 1;
-                        self.settings_focus =
-                            &SETTINGS_LAYOUT[self.settings_position_y][self.settings_position_x];
+                    CurrentScreen::Main => self.pan(0.0, 1.0),
+                },
+                KeyCode::Down => match self.active_screen {
+                    CurrentScreen::Settings => {
+                        if self.settings_position_y + 1 < SETTINGS_LAYOUT.len() {
+                            self.settings_position_y += 1;
+                            self.settings_position_x = self
+                                .settings_position_x
+                                .min(SETTINGS_LAYOUT[self.settings_position_y].len() - 1);
+                            self.settings_focus = &SETTINGS_LAYOUT[self.settings_position_y]
+                                [self.settings_position_x];
+                        }
                     }
-                }
+                    CurrentScreen::Main => self.pan(0.0, -1.0),
+                },
                 KeyCode::Esc => match self.active_screen {
                     CurrentScreen::Main => self.exit(),
                     CurrentScreen::Settings => self.active_screen = CurrentScreen::Main,
@@ -535,11 +1142,118 @@ impl App<'_> {
     fn exit(&mut self) {
         self.exit = true;
     }
+
+    fn config_path() -> Option<PathBuf> {
+        let dirs = ProjectDirs::from("", "Mimmob07", "numerical-integration-calculator")?;
+        Some(dirs.config_dir().join("session.toml"))
+    }
+
+    fn save(&mut self) {
+        let Some(path) = Self::config_path() else {
+            self.last_error = Some("could not determine config directory".to_string());
+            return;
+        };
+
+        let config = SessionConfig {
+            function_text: self.function_text.clone(),
+            bounds_text: self.bounds_text.clone(),
+            window_x_text: self.window_x_text.clone(),
+            window_y_text: self.window_y_text.clone(),
+            dx: self.dx,
+        };
+
+        let contents = match toml::to_string_pretty(&config) {
+            Ok(contents) => contents,
+            Err(err) => {
+                self.last_error = Some(format!("failed to save session: {err}"));
+                return;
+            }
+        };
+
+        let create_dir_result = match path.parent() {
+            Some(parent) => fs::create_dir_all(parent),
+            None => Ok(()),
+        };
+        if let Err(err) = create_dir_result {
+            self.last_error = Some(format!("failed to save session: {err}"));
+            return;
+        }
+
+        match fs::write(&path, contents) {
+            Ok(()) => self.last_error = None,
+            Err(err) => self.last_error = Some(format!("failed to save session: {err}")),
+        }
+    }
+
+    fn load(&mut self) {
+        let Some(path) = Self::config_path() else {
+            return;
+        };
+        let Ok(contents) = fs::read_to_string(&path) else {
+            return;
+        };
+        let Ok(config) = toml::from_str::<SessionConfig>(&contents) else {
+            self.last_error = Some("failed to parse saved session".to_string());
+            return;
+        };
+
+        if config.bounds_text.len() != 2
+            || config.window_x_text.len() != 2
+            || config.window_y_text.len() != 2
+        {
+            self.last_error = Some("saved session has malformed bounds/window fields".to_string());
+            return;
+        }
+
+        // Mirror the Enter handler's validation for `Settings::Function`: a
+        // saved `function_text` must both parse *and* bind as a function of
+        // `x`, or `populate_data`'s `bind("x").unwrap()` would panic on
+        // startup the next time the app loads this config.
+        let expression = match config.function_text.parse::<Expr>() {
+            Ok(expression) => expression,
+            Err(err) => {
+                self.last_error = Some(format!("invalid saved function: {err}"));
+                return;
+            }
+        };
+        if let Err(err) = expression.clone().bind("x") {
+            self.last_error = Some(format!("invalid saved function: {err}"));
+            return;
+        }
+
+        self.function_text = config.function_text;
+        self.expression = expression;
+        self.bounds_text = config.bounds_text;
+        self.window_x_text = config.window_x_text;
+        self.window_y_text = config.window_y_text;
+        self.dx = config.dx.max(MIN_DX);
+
+        for (bound, text) in self.bounds.iter_mut().zip(&self.bounds_text) {
+            if let Ok(value) = text.parse() {
+                *bound = value;
+            }
+        }
+        for (value, text) in self.window_x.iter_mut().zip(&self.window_x_text) {
+            if let Ok(parsed) = text.parse() {
+                *value = parsed;
+            }
+        }
+        for (value, text) in self.window_y.iter_mut().zip(&self.window_y_text) {
+            if let Ok(parsed) = text.parse() {
+                *value = parsed;
+            }
+        }
+
+        self.last_error = None;
+        self.populate_data();
+    }
 }
 
 fn main() -> io::Result<()> {
     let mut termial = ratatui::init();
-    let app_result = App::new().run(&mut termial);
+    let mut app = App::new();
+    app.load();
+    let app_result = app.run(&mut termial);
     ratatui::restore();
     app_result
 }